@@ -1,20 +1,65 @@
-use std::sync::Arc;
+use std::{path::Path, sync::Arc, time::Duration};
 
 use anyhow::{anyhow, Context, Result};
+use crossterm::event::KeyEvent;
+use ratatui::layout::{Constraint, Direction, Layout};
+use serde::{Deserialize, Serialize};
 use tokio::{
-  sync::{mpsc, oneshot, Mutex},
+  sync::{mpsc, oneshot, Mutex, Notify},
   task::JoinHandle,
 };
 use tracing::debug;
 
 use crate::{
-  components::{home::Home, Component},
-  event::EventHandler,
+  components::{fps::FpsCounter, home::Home, Component},
+  config::Config,
+  event::{Event, EventHandler},
+  recording::{self, Recorder},
   terminal::TerminalHandler,
   trace_dbg,
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+type SharedComponent = Arc<Mutex<Box<dyn Component>>>;
+
+/// Events accumulated between wake-ups of `App::run`'s consumer. Redundant
+/// events are collapsed as they arrive: only the latest `Resize` is kept,
+/// and any number of ticks/renders collapse into a single flag, so a burst
+/// of events can never build up an unbounded backlog. Key presses are never
+/// dropped.
+#[derive(Default)]
+struct Pending {
+  keys: Vec<KeyEvent>,
+  resize: Option<(u16, u16)>,
+  tick: bool,
+  render: bool,
+  /// Actions fed in by a replay task, already fully formed and in order.
+  replayed: Vec<Action>,
+}
+
+/// Shared state for an in-progress `App::run_replay`, mutated by
+/// `PauseReplay`/`RestartReplay`/`SetReplaySpeed` as they flow through
+/// `App::dispatch` and read by the replay task before each sleep. `idx` and
+/// `last_offset_ms` track progress through the recording here rather than
+/// in the task's local variables, since a replayed `Action::Suspend` tears
+/// down and respawns the replay task mid-playback; keeping progress here
+/// lets the respawned task pick up where the old one left off instead of
+/// restarting from the beginning.
+struct ReplayState {
+  paused: bool,
+  restart: bool,
+  speed_percent: u16,
+  idx: usize,
+  last_offset_ms: u64,
+}
+
+impl Default for ReplayState {
+  fn default() -> Self {
+    Self { paused: false, restart: false, speed_percent: 100, idx: 0, last_offset_ms: 0 }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
 pub enum Action {
   Quit,
   Resume,
@@ -23,6 +68,7 @@ pub enum Action {
   RenderTick,
   Resize(u16, u16),
   ToggleShowLogger,
+  ToggleShowFps,
   ScheduleIncrementCounter,
   ScheduleDecrementCounter,
   AddToCounter(usize),
@@ -32,6 +78,9 @@ pub enum Action {
   EnterProcessing,
   ExitProcessing,
   Update,
+  PauseReplay,
+  RestartReplay,
+  SetReplaySpeed(u16),
   Noop,
 }
 
@@ -43,20 +92,90 @@ pub enum Message {
 
 pub struct App {
   pub tick_rate: (u64, u64),
-  pub home: Arc<Mutex<Home>>,
+  pub components: Vec<SharedComponent>,
+  pub focused: usize,
+  pub should_quit: bool,
+  pub should_suspend: bool,
+  pub config: Config,
+  /// When `false`, `run` never attaches a terminal: `spawn_tui_task` is
+  /// skipped and `Message::Render` is simply dropped, while event handling,
+  /// dispatch, and suspend/quit still run.
+  pub ui_enabled: bool,
+  pending: Arc<Mutex<Pending>>,
+  notify: Arc<Notify>,
+  recorder: Option<Recorder>,
+  replay: Option<Vec<(u64, Action)>>,
+  replay_state: Arc<Mutex<ReplayState>>,
 }
 
 impl App {
-  pub fn new(tick_rate: (u64, u64)) -> Result<Self> {
-    let home = Arc::new(Mutex::new(Home::new()));
-    Ok(Self { tick_rate, home })
+  /// `tick_rate`, when given, overrides the `tick_rate` loaded from
+  /// `config.toml`/the environment. `ui_enabled` controls whether `run`
+  /// attaches a terminal at all; pass `false` to run the dispatch engine
+  /// headless.
+  pub fn new(tick_rate: Option<(u64, u64)>, ui_enabled: bool) -> Result<Self> {
+    let config = Config::new()?;
+    let home: Box<dyn Component> = Box::new(Home::new());
+    let fps: Box<dyn Component> = Box::new(FpsCounter::new());
+    Ok(Self {
+      tick_rate: tick_rate.unwrap_or(config.tick_rate),
+      components: vec![Arc::new(Mutex::new(home)), Arc::new(Mutex::new(fps))],
+      focused: 0,
+      should_quit: false,
+      should_suspend: false,
+      config,
+      ui_enabled,
+      pending: Arc::new(Mutex::new(Pending::default())),
+      notify: Arc::new(Notify::new()),
+      recorder: None,
+      replay: None,
+      replay_state: Arc::new(Mutex::new(ReplayState::default())),
+    })
   }
 
-  pub fn spawn_tui_task(&mut self) -> (JoinHandle<()>, mpsc::UnboundedSender<Message>) {
-    let home = self.home.clone();
+  /// Run the app exactly like `run`, but tee every dispatched `Action` to
+  /// `path` (newline-delimited JSON with a monotonic offset) for later
+  /// playback with `run_replay`.
+  pub async fn run_record(&mut self, path: impl AsRef<Path>) -> Result<()> {
+    self.recorder = Some(Recorder::create(path)?);
+    self.run().await
+  }
 
+  /// Run the app with input driven entirely by a recording made with
+  /// `run_record`, instead of the terminal: each recorded action fires after
+  /// sleeping its original offset, scaled by `SetReplaySpeed` and held in
+  /// place by `PauseReplay`.
+  pub async fn run_replay(&mut self, path: impl AsRef<Path>) -> Result<()> {
+    self.replay = Some(recording::load(path)?);
+    self.run().await
+  }
+
+  /// Register a component, appending it to the paint/dispatch order. The
+  /// first registered component is focused by default; call `set_focus` to
+  /// change which one receives key input.
+  pub fn register_component(&mut self, component: Box<dyn Component>) {
+    self.components.push(Arc::new(Mutex::new(component)));
+  }
+
+  /// Change which component receives key input. Out-of-range indices are
+  /// ignored.
+  pub fn set_focus(&mut self, index: usize) {
+    if index < self.components.len() {
+      self.focused = index;
+    }
+  }
+
+  pub fn spawn_tui_task(&mut self) -> (JoinHandle<()>, mpsc::UnboundedSender<Message>) {
     let (tui_tx, mut tui_rx) = mpsc::unbounded_channel::<Message>();
 
+    if !self.ui_enabled {
+      // No terminal to attach; drop the receiver so `Message::Render`/`Stop`
+      // sends are silently ignored by the `unwrap_or(())` call sites in `run`.
+      return (tokio::spawn(async {}), tui_tx);
+    }
+
+    let components = self.components.clone();
+
     let tui_task = tokio::spawn(async move {
       let mut tui = TerminalHandler::new().context(anyhow!("Unable to create TUI")).unwrap();
       tui.enter().unwrap();
@@ -64,11 +183,32 @@ impl App {
         match tui_rx.recv().await {
           Some(Message::Stop) => break,
           Some(Message::Render) => {
-            let mut h = home.lock().await;
+            // Acquire every lock up front with `.await`: the `draw` closure
+            // below runs synchronously, where `blocking_lock` would panic.
+            let mut guards = Vec::with_capacity(components.len());
+            for component in &components {
+              guards.push(component.lock().await);
+            }
             tui
               .terminal
               .draw(|f| {
-                h.render(f, f.size());
+                // Give every component but the last an even share of the
+                // screen and pin the last-registered one (the FPS counter
+                // when only `App::new`'s defaults are registered) to a
+                // one-line footer, so registering several
+                // visible components paints a real multi-pane layout
+                // instead of overlapping full-screen draws.
+                let areas = if guards.len() <= 1 {
+                  None
+                } else {
+                  let mut constraints = vec![Constraint::Min(0); guards.len() - 1];
+                  constraints.push(Constraint::Length(1));
+                  Some(Layout::default().direction(Direction::Vertical).constraints(constraints).split(f.size()))
+                };
+                for (i, guard) in guards.iter_mut().enumerate() {
+                  let rect = areas.as_ref().map_or_else(|| f.size(), |areas| areas[i]);
+                  guard.render(f, rect);
+                }
               })
               .unwrap();
           },
@@ -81,16 +221,35 @@ impl App {
     (tui_task, tui_tx)
   }
 
-  pub fn spawn_event_task(&mut self, tx: mpsc::UnboundedSender<Action>) -> (JoinHandle<()>, oneshot::Sender<()>) {
-    let home = self.home.clone();
+  /// Drain input into `self.pending` and wake the consumer in `run` via
+  /// `self.notify`. When a recording is loaded via `run_replay` this plays
+  /// it back on a timer instead of reading the terminal; otherwise it reads
+  /// crossterm events, coalescing redundant ones so a burst of resizes or
+  /// key repeats can't back up `action_rx` with stale frames.
+  pub fn spawn_event_task(&mut self) -> (JoinHandle<()>, oneshot::Sender<()>) {
+    if let Some(recording) = self.replay.clone() {
+      return Self::spawn_replay_task(recording, self.pending.clone(), self.notify.clone(), self.replay_state.clone());
+    }
+
+    let pending = self.pending.clone();
+    let notify = self.notify.clone();
     let (app_tick_rate, render_tick_rate) = self.tick_rate;
     let (stop_event_tx, mut stop_event_rx) = oneshot::channel::<()>();
     let event_task = tokio::spawn(async move {
       let mut events = EventHandler::new(app_tick_rate, render_tick_rate);
       loop {
         let event = events.next().await;
-        let action = home.lock().await.handle_events(event);
-        tx.send(action).unwrap();
+        {
+          let mut pending = pending.lock().await;
+          match event {
+            Some(Event::Key(key)) => pending.keys.push(key),
+            Some(Event::Resize(x, y)) => pending.resize = Some((x, y)),
+            Some(Event::Tick) => pending.tick = true,
+            Some(Event::Render) => pending.render = true,
+            None => {},
+          }
+        }
+        notify.notify_one();
         if stop_event_rx.try_recv().ok().is_some() {
           events.stop().await.unwrap();
           break;
@@ -100,18 +259,132 @@ impl App {
     (event_task, stop_event_tx)
   }
 
+  /// Feed a loaded recording into `pending.replayed` on a timer, honoring
+  /// each action's original offset (scaled by `ReplayState::speed_percent`)
+  /// and holding in place while `ReplayState::paused` is set.
+  fn spawn_replay_task(
+    recording: Vec<(u64, Action)>,
+    pending: Arc<Mutex<Pending>>,
+    notify: Arc<Notify>,
+    replay_state: Arc<Mutex<ReplayState>>,
+  ) -> (JoinHandle<()>, oneshot::Sender<()>) {
+    let (stop_event_tx, mut stop_event_rx) = oneshot::channel::<()>();
+    let event_task = tokio::spawn(async move {
+      loop {
+        if stop_event_rx.try_recv().ok().is_some() {
+          break;
+        }
+
+        let (idx, last_offset_ms, paused, speed_percent) = {
+          let mut state = replay_state.lock().await;
+          if state.restart {
+            state.idx = 0;
+            state.last_offset_ms = 0;
+            state.restart = false;
+          }
+          (state.idx, state.last_offset_ms, state.paused, state.speed_percent.max(1))
+        };
+        if idx >= recording.len() {
+          // Keep polling instead of exiting: a finished replay can still be
+          // restarted (`ReplayState::restart`, handled above) or stopped
+          // (`stop_event_rx`, checked at the top of the loop).
+          tokio::time::sleep(Duration::from_millis(50)).await;
+          continue;
+        }
+        if paused {
+          tokio::time::sleep(Duration::from_millis(50)).await;
+          continue;
+        }
+
+        let (offset_ms, action) = recording[idx].clone();
+        let delay_ms = offset_ms.saturating_sub(last_offset_ms) * 100 / speed_percent as u64;
+        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+
+        pending.lock().await.replayed.push(action);
+        notify.notify_one();
+
+        let mut state = replay_state.lock().await;
+        state.idx = idx + 1;
+        state.last_offset_ms = offset_ms;
+      }
+    });
+    (event_task, stop_event_tx)
+  }
+
+  /// Take the whole coalesced batch out of `self.pending` and turn it into
+  /// `Action`s, resolving keys against the configured keybindings (falling
+  /// back to the focused component) before pushing onto `action_tx`.
+  async fn drain_pending_events(&self, action_tx: &mpsc::UnboundedSender<Action>) -> Result<()> {
+    let drained = {
+      let mut pending = self.pending.lock().await;
+      std::mem::take(&mut *pending)
+    };
+
+    for key in drained.keys {
+      if let Some(action) = self.config.action_for_key(key) {
+        action_tx.send(action)?;
+        continue;
+      }
+      for (i, component) in self.components.iter().enumerate() {
+        if i == self.focused {
+          action_tx.send(component.lock().await.handle_events(Some(Event::Key(key))))?;
+        }
+      }
+    }
+    if let Some((x, y)) = drained.resize {
+      action_tx.send(Action::Resize(x, y))?;
+    }
+    if drained.tick {
+      action_tx.send(Action::Tick)?;
+    }
+    if drained.render {
+      action_tx.send(Action::RenderTick)?;
+    }
+    for action in drained.replayed {
+      action_tx.send(action)?;
+    }
+    Ok(())
+  }
+
+  /// Feed `action` to every registered component, forwarding any follow-up
+  /// action each one returns back onto `action_tx`, and update the app-level
+  /// quit/suspend flags in response to the corresponding global actions.
+  async fn dispatch(&mut self, action: Action, action_tx: &mpsc::UnboundedSender<Action>) -> Result<()> {
+    if let Some(recorder) = &mut self.recorder {
+      recorder.record(action)?;
+    }
+    match action {
+      Action::Quit => self.should_quit = true,
+      Action::Suspend => self.should_suspend = true,
+      Action::Resume => self.should_suspend = false,
+      Action::PauseReplay => self.replay_state.lock().await.paused ^= true,
+      Action::RestartReplay => self.replay_state.lock().await.restart = true,
+      Action::SetReplaySpeed(percent) => self.replay_state.lock().await.speed_percent = percent,
+      _ => {},
+    }
+    for component in &self.components {
+      if let Some(follow_up) = component.lock().await.dispatch(action) {
+        action_tx.send(follow_up)?;
+      }
+    }
+    Ok(())
+  }
+
   pub async fn run(&mut self) -> Result<()> {
     let (action_tx, mut action_rx) = mpsc::unbounded_channel();
 
-    self.home.lock().await.action_tx = Some(action_tx.clone());
-
-    self.home.lock().await.init()?;
+    for component in &self.components {
+      component.lock().await.init()?;
+    }
 
     let (mut tui_task, mut tui_tx) = self.spawn_tui_task();
-    let (mut event_task, mut stop_event_tx) = self.spawn_event_task(action_tx.clone());
+    let (mut event_task, mut stop_event_tx) = self.spawn_event_task();
 
     loop {
-      let mut maybe_action = action_rx.recv().await;
+      self.notify.notified().await;
+      self.drain_pending_events(&action_tx).await?;
+
+      let mut maybe_action = action_rx.try_recv().ok();
       while maybe_action.is_some() {
         let action = maybe_action.unwrap();
         if action == Action::RenderTick {
@@ -119,25 +392,26 @@ impl App {
         } else if action != Action::Tick {
           trace_dbg!(action.clone());
         }
-        if let Some(a) = self.home.lock().await.dispatch(action) {
-          action_tx.send(a)?
-        };
+        self.dispatch(action, &action_tx).await?;
         maybe_action = action_rx.try_recv().ok();
       }
 
-      if self.home.lock().await.should_suspend {
+      if self.should_suspend {
         tui_tx.send(Message::Stop).unwrap_or(());
         stop_event_tx.send(()).unwrap_or(());
         tui_task.await?;
         event_task.await?;
-        let tui = TerminalHandler::new().context(anyhow!("Unable to create TUI")).unwrap();
-        tui.suspend()?; // Blocks here till process resumes on Linux and Mac.
-                        // TODO: figure out appropriate behaviour on Windows.
-        debug!("resuming");
+        if self.ui_enabled {
+          let tui = TerminalHandler::new().context(anyhow!("Unable to create TUI")).unwrap();
+          tui.suspend()?; // Blocks here till process resumes on Linux and Mac.
+                          // TODO: figure out appropriate behaviour on Windows.
+          debug!("resuming");
+        }
         (tui_task, tui_tx) = self.spawn_tui_task();
-        (event_task, stop_event_tx) = self.spawn_event_task(action_tx.clone());
+        (event_task, stop_event_tx) = self.spawn_event_task();
         action_tx.send(Action::Resume)?;
-      } else if self.home.lock().await.should_quit {
+        self.notify.notify_one(); // wake the loop's next `notified()` even with no pending input event
+      } else if self.should_quit {
         tui_tx.send(Message::Stop).unwrap_or(());
         stop_event_tx.send(()).unwrap_or(());
         tui_task.await?;
@@ -148,3 +422,51 @@ impl App {
     Ok(())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn replay_task_restarts_instead_of_exiting_once_finished() {
+    let pending = Arc::new(Mutex::new(Pending::default()));
+    let notify = Arc::new(Notify::new());
+    let replay_state = Arc::new(Mutex::new(ReplayState::default()));
+
+    let (task, stop_tx) =
+      App::spawn_replay_task(vec![(0, Action::Tick)], pending.clone(), notify.clone(), replay_state.clone());
+
+    notify.notified().await;
+    assert_eq!(std::mem::take(&mut pending.lock().await.replayed), vec![Action::Tick]);
+
+    // The recording is exhausted, but the task must keep polling rather
+    // than exit, or a restart requested now would have nothing to observe it.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(!task.is_finished());
+
+    replay_state.lock().await.restart = true;
+    notify.notified().await;
+    assert_eq!(pending.lock().await.replayed, vec![Action::Tick]);
+
+    stop_tx.send(()).unwrap();
+    task.await.unwrap();
+  }
+
+  #[tokio::test]
+  async fn replay_task_scales_delay_by_speed_percent() {
+    let pending = Arc::new(Mutex::new(Pending::default()));
+    let notify = Arc::new(Notify::new());
+    let replay_state = Arc::new(Mutex::new(ReplayState { speed_percent: 1000, ..ReplayState::default() }));
+
+    let started = std::time::Instant::now();
+    let (task, stop_tx) =
+      App::spawn_replay_task(vec![(200, Action::Tick)], pending.clone(), notify.clone(), replay_state.clone());
+    notify.notified().await;
+
+    // 200ms at 1000% speed should fire in ~20ms, nowhere near the original offset.
+    assert!(started.elapsed() < Duration::from_millis(100));
+
+    stop_tx.send(()).unwrap();
+    task.await.unwrap();
+  }
+}
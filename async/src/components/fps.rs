@@ -0,0 +1,96 @@
+use std::time::Instant;
+
+use ratatui::{layout::Rect, widgets::Paragraph, Frame};
+
+use super::Component;
+use crate::app::Action;
+
+/// Smoothed `Action::Tick`/`Action::RenderTick` rate, rendered as an
+/// overlay when toggled on.
+pub struct FpsCounter {
+  show_fps: bool,
+  last_instant: Instant,
+  app_tick_count: u32,
+  render_tick_count: u32,
+  app_tick_rate: f64,
+  render_tick_rate: f64,
+}
+
+impl Default for FpsCounter {
+  fn default() -> Self {
+    Self {
+      show_fps: false,
+      last_instant: Instant::now(),
+      app_tick_count: 0,
+      render_tick_count: 0,
+      app_tick_rate: 0.0,
+      render_tick_rate: 0.0,
+    }
+  }
+}
+
+impl FpsCounter {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  fn maybe_recompute(&mut self) {
+    let elapsed = self.last_instant.elapsed().as_secs_f64();
+    if elapsed >= 1.0 {
+      self.app_tick_rate = self.app_tick_count as f64 / elapsed;
+      self.render_tick_rate = self.render_tick_count as f64 / elapsed;
+      self.app_tick_count = 0;
+      self.render_tick_count = 0;
+      self.last_instant = Instant::now();
+    }
+  }
+}
+
+impl Component for FpsCounter {
+  fn dispatch(&mut self, action: Action) -> Option<Action> {
+    match action {
+      Action::Tick => self.app_tick_count += 1,
+      Action::RenderTick => self.render_tick_count += 1,
+      Action::ToggleShowFps => self.show_fps = !self.show_fps,
+      _ => (),
+    }
+    self.maybe_recompute();
+    None
+  }
+
+  fn render(&mut self, f: &mut Frame<'_>, rect: Rect) {
+    if !self.show_fps {
+      return;
+    }
+    let text = format!("{:.2} ticks/s, {:.2} frames/s", self.app_tick_rate, self.render_tick_rate);
+    f.render_widget(Paragraph::new(text), rect);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn recomputes_rate_once_a_second_has_elapsed() {
+    let mut fps = FpsCounter::new();
+    fps.app_tick_count = 3;
+    fps.last_instant = Instant::now() - std::time::Duration::from_millis(1500);
+
+    fps.maybe_recompute();
+
+    assert!(fps.app_tick_rate > 0.0);
+    assert_eq!(fps.app_tick_count, 0);
+  }
+
+  #[test]
+  fn does_not_recompute_before_a_second_has_elapsed() {
+    let mut fps = FpsCounter::new();
+    fps.app_tick_count = 3;
+
+    fps.maybe_recompute();
+
+    assert_eq!(fps.app_tick_rate, 0.0);
+    assert_eq!(fps.app_tick_count, 3);
+  }
+}
@@ -0,0 +1,56 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  layout::Rect,
+  widgets::{Block, Borders, Paragraph},
+  Frame,
+};
+
+use super::Component;
+use crate::app::Action;
+
+#[derive(Default)]
+pub struct Home {
+  pub show_logger: bool,
+  pub counter: usize,
+}
+
+impl Home {
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+impl Component for Home {
+  fn init(&mut self) -> Result<()> {
+    Ok(())
+  }
+
+  fn handle_key(&mut self, key: KeyEvent) -> Action {
+    match key.code {
+      KeyCode::Char('q') => Action::Quit,
+      KeyCode::Char('z') => Action::Suspend,
+      KeyCode::Char('l') => Action::ToggleShowLogger,
+      KeyCode::Char('f') => Action::ToggleShowFps,
+      KeyCode::Char('j') | KeyCode::Down => Action::ScheduleDecrementCounter,
+      KeyCode::Char('k') | KeyCode::Up => Action::ScheduleIncrementCounter,
+      _ => Action::Noop,
+    }
+  }
+
+  fn dispatch(&mut self, action: Action) -> Option<Action> {
+    match action {
+      Action::ToggleShowLogger => self.show_logger = !self.show_logger,
+      Action::ScheduleIncrementCounter => return Some(Action::AddToCounter(1)),
+      Action::ScheduleDecrementCounter => return Some(Action::SubtractFromCounter(1)),
+      Action::AddToCounter(n) => self.counter += n,
+      Action::SubtractFromCounter(n) => self.counter = self.counter.saturating_sub(n),
+      _ => (),
+    }
+    None
+  }
+
+  fn render(&mut self, f: &mut Frame<'_>, rect: Rect) {
+    f.render_widget(Paragraph::new(format!("counter: {}", self.counter)).block(Block::default().borders(Borders::ALL)), rect);
+  }
+}
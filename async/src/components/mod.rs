@@ -0,0 +1,44 @@
+pub mod fps;
+pub mod home;
+
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use ratatui::{layout::Rect, Frame};
+
+use crate::{app::Action, event::Event};
+
+/// `Component` is the building block for anything that can be registered on
+/// `App` and take part in the render/dispatch loop: a main view, a log
+/// panel, a status bar, etc. `App::focused` is the sole authority over
+/// which registered component receives raw key input; every component
+/// receives every dispatched `Action`.
+pub trait Component: Send {
+  fn init(&mut self) -> Result<()> {
+    Ok(())
+  }
+
+  /// Turn a raw terminal/tick event into an `Action`. Components that don't
+  /// care about a particular event should return `Action::Noop`. Only
+  /// called with `Event::Key` for the component `App::focused` points at.
+  fn handle_events(&mut self, event: Option<Event>) -> Action {
+    match event {
+      Some(Event::Key(key_event)) => self.handle_key(key_event),
+      Some(Event::Render) => Action::RenderTick,
+      Some(Event::Tick) => Action::Tick,
+      Some(Event::Resize(x, y)) => Action::Resize(x, y),
+      _ => Action::Noop,
+    }
+  }
+
+  fn handle_key(&mut self, key: KeyEvent) -> Action {
+    let _ = key;
+    Action::Noop
+  }
+
+  fn dispatch(&mut self, action: Action) -> Option<Action> {
+    let _ = action;
+    None
+  }
+
+  fn render(&mut self, f: &mut Frame<'_>, rect: Rect);
+}
@@ -0,0 +1,106 @@
+use std::{collections::HashMap, env, path::PathBuf};
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use figment::{
+  providers::{Env, Format, Serialized, Toml},
+  Figment,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::app::Action;
+
+const CONFIG_FILE_NAME: &str = "config.toml";
+const ENV_PREFIX: &str = "APP_";
+
+/// App configuration, loaded from (in increasing priority) built-in
+/// defaults, `config.toml` in the current working directory (or the file
+/// named by `APP_CONFIG`), and `APP_`-prefixed environment variables.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Config {
+  #[serde(default = "default_tick_rate")]
+  pub tick_rate: (u64, u64),
+  /// Maps key chords (e.g. `"q"`, `"ctrl-c"`, `"j"`) to the `Action` they
+  /// should raise, read straight off the `[keybindings]` TOML table.
+  #[serde(default)]
+  pub keybindings: HashMap<String, Action>,
+}
+
+fn default_tick_rate() -> (u64, u64) {
+  (4, 60)
+}
+
+impl Default for Config {
+  fn default() -> Self {
+    Self { tick_rate: default_tick_rate(), keybindings: HashMap::new() }
+  }
+}
+
+impl Config {
+  pub fn new() -> Result<Self> {
+    let config: Config = Figment::new()
+      .merge(Serialized::defaults(Config::default()))
+      .merge(Toml::file(config_path()))
+      .merge(Env::prefixed(ENV_PREFIX))
+      .extract()?;
+    Ok(config)
+  }
+
+  /// Look up the `Action` bound to a raw key event, if any.
+  pub fn action_for_key(&self, key: KeyEvent) -> Option<Action> {
+    self.keybindings.get(&key_event_to_string(key)).copied()
+  }
+}
+
+fn config_path() -> PathBuf {
+  env::var(format!("{ENV_PREFIX}CONFIG")).map(PathBuf::from).unwrap_or_else(|_| PathBuf::from(CONFIG_FILE_NAME))
+}
+
+/// Render a `KeyEvent` the same way a user would spell it in `config.toml`,
+/// e.g. `ctrl-c`, `shift-tab`, `j`.
+fn key_event_to_string(key: KeyEvent) -> String {
+  let mut parts = Vec::new();
+  if key.modifiers.contains(KeyModifiers::CONTROL) {
+    parts.push("ctrl".to_string());
+  }
+  if key.modifiers.contains(KeyModifiers::ALT) {
+    parts.push("alt".to_string());
+  }
+  if key.modifiers.contains(KeyModifiers::SHIFT) {
+    parts.push("shift".to_string());
+  }
+  parts.push(match key.code {
+    KeyCode::Char(c) => c.to_string(),
+    KeyCode::Esc => "esc".to_string(),
+    KeyCode::Enter => "enter".to_string(),
+    KeyCode::Left => "left".to_string(),
+    KeyCode::Right => "right".to_string(),
+    KeyCode::Up => "up".to_string(),
+    KeyCode::Down => "down".to_string(),
+    KeyCode::Backspace => "backspace".to_string(),
+    KeyCode::Tab => "tab".to_string(),
+    other => format!("{other:?}").to_lowercase(),
+  });
+  parts.join("-")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn formats_plain_and_modified_chords() {
+    assert_eq!(key_event_to_string(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE)), "j");
+    assert_eq!(key_event_to_string(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)), "ctrl-c");
+    assert_eq!(key_event_to_string(KeyEvent::new(KeyCode::Tab, KeyModifiers::SHIFT)), "shift-tab");
+  }
+
+  #[test]
+  fn action_for_key_resolves_through_keybindings() {
+    let mut config = Config::default();
+    config.keybindings.insert("q".to_string(), Action::Quit);
+
+    assert_eq!(config.action_for_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)), Some(Action::Quit));
+    assert_eq!(config.action_for_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE)), None);
+  }
+}
@@ -0,0 +1,74 @@
+use std::{
+  fs::File,
+  io::{BufRead, BufReader, BufWriter, Write},
+  path::Path,
+  time::Instant,
+};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::app::Action;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedAction {
+  offset_ms: u64,
+  action: Action,
+}
+
+/// Tees every dispatched `Action` to an append-only, newline-delimited JSON
+/// file together with how long after the recording started it fired, so a
+/// session can later be replayed deterministically with `App::run_replay`.
+pub struct Recorder {
+  writer: BufWriter<File>,
+  start: Instant,
+}
+
+impl Recorder {
+  pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+    Ok(Self { writer: BufWriter::new(File::create(path)?), start: Instant::now() })
+  }
+
+  pub fn record(&mut self, action: Action) -> Result<()> {
+    let entry = RecordedAction { offset_ms: self.start.elapsed().as_millis() as u64, action };
+    serde_json::to_writer(&mut self.writer, &entry)?;
+    self.writer.write_all(b"\n")?;
+    self.writer.flush()?;
+    Ok(())
+  }
+}
+
+/// Load a recording written by `Recorder` back into an ordered list of
+/// `(offset_ms, action)` pairs.
+pub fn load(path: impl AsRef<Path>) -> Result<Vec<(u64, Action)>> {
+  BufReader::new(File::open(path)?)
+    .lines()
+    .map(|line| {
+      let entry: RecordedAction = serde_json::from_str(&line?)?;
+      Ok((entry.offset_ms, entry.action))
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn round_trips_recorded_actions_in_order() {
+    let path = std::env::temp_dir().join(format!("rust-tui-template-recording-test-{}.jsonl", std::process::id()));
+
+    let mut recorder = Recorder::create(&path).unwrap();
+    recorder.record(Action::Tick).unwrap();
+    recorder.record(Action::Quit).unwrap();
+    drop(recorder);
+
+    let loaded = load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[0].1, Action::Tick);
+    assert_eq!(loaded[1].1, Action::Quit);
+    assert!(loaded[0].0 <= loaded[1].0);
+  }
+}